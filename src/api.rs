@@ -0,0 +1,95 @@
+//! The `/api/v1` reputation API, documented with `aide` so the OpenAPI spec
+//! at `/api/openapi.json` and the `/docs` UI stay in sync with the handlers.
+//! The schema route itself lives one level up, outside `/api/v1` — see
+//! `main.rs`.
+
+use std::sync::Arc;
+
+use aide::axum::routing::get_with;
+use aide::axum::ApiRouter;
+use aide::transform::TransformOperation;
+use axum::extract::{Path, Query};
+use axum::{Extension, Json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::relay::{IngestionStatus, RelayHealth};
+use crate::score::{PeerScore, ScoreStore};
+
+/// Sort order for the leaderboard endpoint.
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardSort {
+    #[default]
+    ScoreDesc,
+    ScoreAsc,
+    TotalTrades,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LeaderboardQuery {
+    /// Maximum number of peers to return. Defaults to 50.
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub sort: LeaderboardSort,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Leaderboard {
+    pub peers: Vec<PeerScore>,
+}
+
+async fn get_score(
+    Extension(store): Extension<Arc<ScoreStore>>,
+    Path(pubkey): Path<String>,
+) -> Json<PeerScore> {
+    Json(store.score_for(&pubkey))
+}
+
+fn get_score_docs(op: TransformOperation) -> TransformOperation {
+    op.description("Returns the reputation score for a single peer pubkey.")
+        .response::<200, Json<PeerScore>>()
+}
+
+async fn get_leaderboard(
+    Extension(store): Extension<Arc<ScoreStore>>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Json<Leaderboard> {
+    let mut peers = store.all_scores();
+    match query.sort {
+        LeaderboardSort::ScoreDesc => {
+            peers.sort_by(|a, b| b.score.total_cmp(&a.score));
+        }
+        LeaderboardSort::ScoreAsc => {
+            peers.sort_by(|a, b| a.score.total_cmp(&b.score));
+        }
+        LeaderboardSort::TotalTrades => {
+            peers.sort_by(|a, b| b.total_trades.cmp(&a.total_trades));
+        }
+    }
+    peers.truncate(query.limit.unwrap_or(50));
+    Json(Leaderboard { peers })
+}
+
+fn get_leaderboard_docs(op: TransformOperation) -> TransformOperation {
+    op.description("Returns the top-scored peers, optionally sorted and limited.")
+        .response::<200, Json<Leaderboard>>()
+}
+
+async fn get_health(Extension(status): Extension<Arc<IngestionStatus>>) -> Json<RelayHealth> {
+    Json(status.snapshot())
+}
+
+fn get_health_docs(op: TransformOperation) -> TransformOperation {
+    op.description("Reports relay connection status and the last ingested event time.")
+        .response::<200, Json<RelayHealth>>()
+}
+
+/// Builds the `/api/v1` scores/health router, to be nested under the main
+/// application router.
+pub fn router() -> ApiRouter {
+    ApiRouter::new()
+        .api_route("/scores/:pubkey", get_with(get_score, get_score_docs))
+        .api_route("/scores", get_with(get_leaderboard, get_leaderboard_docs))
+        .api_route("/health", get_with(get_health, get_health_docs))
+}
@@ -0,0 +1,97 @@
+//! Runtime configuration.
+//!
+//! Settings are read from `config.toml` in the working directory and then
+//! overridden by environment variables under the `APP_` prefix, using `__`
+//! (double underscore) to separate nested keys so it doesn't collide with
+//! the single underscores inside field names themselves: `APP_SERVER__HOST`,
+//! `APP_SERVER__PORT`, `APP_SERVER__STATIC_DIR`, `APP_CORS__ALLOWED_ORIGINS`,
+//! `APP_NOSTR__RELAYS`. This lets the server be deployed behind a real
+//! domain without recompiling.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub static_dir: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NostrConfig {
+    pub relays: Vec<String>,
+    /// Mostro instance pubkey(s) whose rating events are trusted. Events
+    /// from any other author are ignored, so unrelated or forged
+    /// kind-38383 events can't feed the store.
+    pub rating_authors: Vec<String>,
+}
+
+/// TLS termination settings. Absent (or with `cert_path`/`key_path` unset)
+/// means the server stays plaintext.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TlsConfig {
+    pub cert_path: Option<String>,
+    pub key_path: Option<String>,
+    /// Port for a plain-HTTP listener that 301-redirects to HTTPS.
+    /// Only used when `cert_path`/`key_path` are set.
+    pub redirect_http_port: Option<u16>,
+}
+
+impl TlsConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.cert_path.is_some() && self.key_path.is_some()
+    }
+}
+
+/// NIP-05 identity mapping, served at `/.well-known/nostr.json`: local
+/// part (the bit before `@`) to Mostro pubkey.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Nip05Config {
+    #[serde(default)]
+    pub names: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub cors: CorsConfig,
+    pub nostr: NostrConfig,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    #[serde(default)]
+    pub nip05: Nip05Config,
+}
+
+impl AppConfig {
+    /// Loads `config.toml`, then applies environment variable overrides.
+    /// The `--dev` flag is not part of the config: `main` reads it and uses
+    /// it to pick a permissive CORS policy instead of the configured allowlist.
+    pub fn load() -> anyhow::Result<Self> {
+        let settings = config::Config::builder()
+            .add_source(config::File::with_name("config").required(false))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .separator("__")
+                    .list_separator(",")
+                    .with_list_parse_key("cors.allowed_origins")
+                    .with_list_parse_key("nostr.relays")
+                    .with_list_parse_key("nostr.rating_authors")
+                    .try_parsing(true),
+            )
+            .build()?;
+
+        Ok(settings.try_deserialize()?)
+    }
+
+    pub fn bind_addr(&self) -> anyhow::Result<SocketAddr> {
+        Ok(format!("{}:{}", self.server.host, self.server.port).parse()?)
+    }
+}
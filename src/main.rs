@@ -1,24 +1,114 @@
-use axum::Router;
-use std::net::SocketAddr;
-use tower_http::cors::{Any, CorsLayer};
+mod api;
+mod config;
+mod relay;
+mod score;
+mod tls;
+mod wellknown;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use aide::axum::ApiRouter;
+use aide::openapi::OpenApi;
+use aide::redoc::Redoc;
+use axum::extract::DefaultBodyLimit;
+use axum::http::HeaderValue;
+use axum::{Extension, Json, Router};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::services::ServeDir;
+use tower_http::timeout::TimeoutLayer;
+use tower_http::trace::TraceLayer;
+use tracing_subscriber::EnvFilter;
+
+use config::AppConfig;
+use score::ScoreStore;
+
+/// Request timeout applied to every route.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum accepted request body size (1 MiB); the scoring API only ever
+/// receives small JSON bodies.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Serves the generated OpenAPI document at the top-level `/api/openapi.json`,
+/// which is what `Redoc` below is pointed at.
+async fn openapi_json(Extension(api): Extension<Arc<OpenApi>>) -> Json<OpenApi> {
+    Json(api.as_ref().clone())
+}
 
 #[tokio::main]
 async fn main() {
-    // CORS for development
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    // Serve static files from the web/ directory
-    let app = Router::new()
-        .fallback_service(ServeDir::new("web"))
-        .layer(cors);
-
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    println!("Mostro Score Web running at http://{}", addr);
-
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let dev_mode = std::env::args().any(|arg| arg == "--dev");
+
+    let config = AppConfig::load().expect("failed to load config.toml");
+
+    let cors = if dev_mode {
+        CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods(Any)
+            .allow_headers(Any)
+    } else {
+        let origins: Vec<HeaderValue> = config
+            .cors
+            .allowed_origins
+            .iter()
+            .map(|origin| {
+                origin
+                    .parse()
+                    .unwrap_or_else(|_| panic!("invalid CORS origin: {origin}"))
+            })
+            .collect();
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods(Any)
+            .allow_headers(Any)
+    };
+
+    let store = Arc::new(ScoreStore::new());
+    let ingestion_status = relay::spawn(
+        config.nostr.relays.clone(),
+        config.nostr.rating_authors.clone(),
+        store.clone(),
+    );
+    let nip05 = Arc::new(config.nip05.clone());
+
+    let mut api = OpenApi::default();
+
+    let app = ApiRouter::new()
+        .nest_api_service("/api/v1", api::router())
+        .nest_service("/.well-known", wellknown::router())
+        .route("/api/openapi.json", axum::routing::get(openapi_json))
+        .route(
+            "/docs",
+            axum::routing::get(Redoc::new("/api/openapi.json").axum_route()),
+        )
+        .finish_api(&mut api)
+        .layer(Extension(Arc::new(api)))
+        .layer(Extension(store))
+        .layer(Extension(ingestion_status))
+        .layer(Extension(nip05))
+        .fallback_service(ServeDir::new(config.server.static_dir.clone()))
+        .layer(cors)
+        .layer(TraceLayer::new_for_http())
+        .layer(CompressionLayer::new())
+        .layer(TimeoutLayer::new(REQUEST_TIMEOUT))
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES));
+
+    let app: Router = app.into();
+
+    let addr = config.bind_addr().expect("invalid server host/port");
+
+    if config.tls.is_enabled() {
+        tracing::info!(%addr, "Mostro Score Web running");
+        tls::serve(addr, &config.tls, app).await;
+    } else {
+        tracing::info!(%addr, "Mostro Score Web running");
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        axum::serve(listener, app).await.unwrap();
+    }
 }
@@ -0,0 +1,242 @@
+//! Nostr relay ingestion.
+//!
+//! Connects to a configurable set of relays, subscribes to Mostro
+//! rating/order-confirmation events, and feeds parsed ratings into the
+//! shared [`ScoreStore`]. Runs as a background tokio task spawned from
+//! `main` before `axum::serve` starts accepting connections.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use nostr_sdk::{Client, Event, Filter, Kind, PublicKey, RelayPoolNotification, RelayStatus};
+use schemars::JsonSchema;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::score::ScoreStore;
+
+/// Kind used by Mostro for order rating/confirmation events.
+const MOSTRO_RATING_KIND: Kind = Kind::Custom(38383);
+
+/// Base delay for the reconnect backoff; doubles on each consecutive
+/// failure, capped at `MAX_BACKOFF`.
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A connection that stays up at least this long is considered stable and
+/// resets the backoff; anything shorter keeps growing it, so a flapping
+/// relay doesn't produce a hot reconnect loop.
+const STABLE_CONNECTION_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// How often the relay pool's connection status is re-polled for
+/// `/api/v1/health`.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum number of event ids kept for dedup, across reconnects. Bounded
+/// (oldest evicted first) so long-running ingestion doesn't leak memory.
+const SEEN_EVENT_CAPACITY: usize = 10_000;
+
+/// Bounded, insertion-ordered set of recently-seen event ids, kept across
+/// reconnects so a relay re-sending events after a drop doesn't re-ingest
+/// them.
+struct SeenEvents {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl SeenEvents {
+    fn new() -> Self {
+        Self {
+            ids: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Returns `true` if `id` was not already seen, recording it.
+    fn insert(&mut self, id: String) -> bool {
+        if !self.ids.insert(id.clone()) {
+            return false;
+        }
+        self.order.push_back(id);
+        if self.order.len() > SEEN_EVENT_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// Connection status for the `/api/v1/health` endpoint.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RelayHealth {
+    pub relays_connected: usize,
+    pub relays_configured: usize,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schemars(with = "Option<String>")]
+    pub last_ingested_at: Option<OffsetDateTime>,
+}
+
+/// Shared, lock-protected ingestion status updated by the background task
+/// and read by the health handler.
+#[derive(Debug, Default)]
+pub struct IngestionStatus {
+    relays_connected: RwLock<usize>,
+    relays_configured: RwLock<usize>,
+    last_ingested_at: RwLock<Option<OffsetDateTime>>,
+}
+
+impl IngestionStatus {
+    pub fn snapshot(&self) -> RelayHealth {
+        RelayHealth {
+            relays_connected: *self.relays_connected.read().expect("status poisoned"),
+            relays_configured: *self.relays_configured.read().expect("status poisoned"),
+            last_ingested_at: *self.last_ingested_at.read().expect("status poisoned"),
+        }
+    }
+}
+
+/// Spawns the relay ingestion task and returns the shared status handle
+/// used by the health endpoint. `rating_authors` are the Mostro instance
+/// pubkeys whose rating events are trusted; events from any other author
+/// are dropped by the subscription filter.
+pub fn spawn(
+    relay_urls: Vec<String>,
+    rating_authors: Vec<String>,
+    store: Arc<ScoreStore>,
+) -> Arc<IngestionStatus> {
+    let status = Arc::new(IngestionStatus::default());
+    *status.relays_configured.write().expect("status poisoned") = relay_urls.len();
+
+    let authors: Vec<PublicKey> = rating_authors
+        .iter()
+        .map(|key| {
+            key.parse()
+                .unwrap_or_else(|_| panic!("invalid nostr.rating_authors pubkey: {key}"))
+        })
+        .collect();
+
+    let task_status = status.clone();
+    tokio::spawn(async move {
+        run_with_reconnect(relay_urls, authors, store, task_status).await;
+    });
+
+    status
+}
+
+async fn run_with_reconnect(
+    relay_urls: Vec<String>,
+    authors: Vec<PublicKey>,
+    store: Arc<ScoreStore>,
+    status: Arc<IngestionStatus>,
+) {
+    let mut backoff = BASE_BACKOFF;
+    let mut seen_events = SeenEvents::new();
+
+    loop {
+        let connected_at = Instant::now();
+        if let Err(err) =
+            ingest_once(&relay_urls, &authors, &store, &status, &mut seen_events).await
+        {
+            tracing::warn!(?err, "relay ingestion error");
+        } else {
+            tracing::warn!("relay notification stream closed");
+        }
+        *status.relays_connected.write().expect("status poisoned") = 0;
+
+        // Both a clean stream close and an error mean we're disconnected;
+        // either way we reconnect through the backoff below. Only a
+        // connection that stayed up a while resets it, so a relay that
+        // keeps dropping doesn't cause a hot retry loop.
+        if connected_at.elapsed() >= STABLE_CONNECTION_THRESHOLD {
+            backoff = BASE_BACKOFF;
+        } else {
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+
+        tracing::warn!(?backoff, "reconnecting to relays");
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+async fn ingest_once(
+    relay_urls: &[String],
+    authors: &[PublicKey],
+    store: &Arc<ScoreStore>,
+    status: &Arc<IngestionStatus>,
+    seen_events: &mut SeenEvents,
+) -> anyhow::Result<()> {
+    let client = Client::default();
+    for url in relay_urls {
+        client.add_relay(url.clone()).await?;
+    }
+    client.connect().await;
+
+    let filter = Filter::new()
+        .kind(MOSTRO_RATING_KIND)
+        .authors(authors.to_vec());
+    client.subscribe(vec![filter], None).await?;
+
+    let mut notifications = client.notifications();
+    let mut status_poll = tokio::time::interval(STATUS_POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = status_poll.tick() => {
+                update_connected_count(&client, status).await;
+            }
+            notification = notifications.recv() => {
+                match notification {
+                    Ok(RelayPoolNotification::Event { event, .. }) => {
+                        if seen_events.insert(event.id.to_string()) {
+                            handle_event(&event, store);
+                            *status.last_ingested_at.write().expect("status poisoned") =
+                                Some(OffsetDateTime::now_utc());
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) => return Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Counts relays the pool currently reports as `Connected` and publishes
+/// it for the health endpoint, rather than assuming `connect()` succeeded
+/// for every configured relay.
+async fn update_connected_count(client: &Client, status: &Arc<IngestionStatus>) {
+    let connected = client
+        .relays()
+        .await
+        .values()
+        .filter(|relay| relay.status() == RelayStatus::Connected)
+        .count();
+    *status.relays_connected.write().expect("status poisoned") = connected;
+}
+
+/// Parses a Mostro rating event and records it in the store. Malformed
+/// events (missing pubkey tag or out-of-range stars) are dropped.
+fn handle_event(event: &Event, store: &Arc<ScoreStore>) {
+    let Some(rated_pubkey) = event.tags.iter().find_map(|tag| {
+        let values = tag.as_vec();
+        (values.first().map(String::as_str) == Some("p"))
+            .then(|| values.get(1).cloned())
+            .flatten()
+    }) else {
+        return;
+    };
+
+    let Ok(stars) = event.content.trim().parse::<f64>() else {
+        return;
+    };
+    if !(1.0..=5.0).contains(&stars) {
+        return;
+    }
+
+    let rated_at = OffsetDateTime::from_unix_timestamp(event.created_at.as_u64() as i64)
+        .unwrap_or_else(|_| OffsetDateTime::now_utc());
+
+    store.record_rating(&rated_pubkey, stars, rated_at);
+}
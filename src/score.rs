@@ -0,0 +1,151 @@
+//! Reputation scoring for Mostro peers.
+//!
+//! Scores are a time-decayed Bayesian average over per-trade star ratings
+//! (1-5). Older ratings are discounted with an exponential half-life so a
+//! peer's score reflects recent behaviour, while peers with few trades are
+//! pulled toward the global prior so a single rating can't swing a score to
+//! the extremes.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+/// Half-life of ~180 days expressed as a per-day decay rate: ln(2) / 180.
+const DECAY_LAMBDA: f64 = 0.003_851;
+
+/// Prior weight: how many "phantom" ratings at the global mean a peer with
+/// no history starts with. Damps scores for peers with few trades.
+const PRIOR_WEIGHT: f64 = 5.0;
+
+/// Fallback prior `m` used only until any ratings exist at all; once there
+/// is data, `m` is the actual mean of every stored rating (see
+/// `ScoreStore::global_mean`).
+const DEFAULT_PRIOR_MEAN: f64 = 3.0;
+
+/// A single star rating left for a peer after a completed trade.
+#[derive(Debug, Clone)]
+pub struct Rating {
+    pub stars: f64,
+    pub rated_at: OffsetDateTime,
+}
+
+/// All ratings collected for one pubkey.
+#[derive(Debug, Default, Clone)]
+pub struct PeerRatings {
+    pub ratings: Vec<Rating>,
+}
+
+/// In-memory store of ratings per pubkey, shared across the API and the
+/// relay ingestion subsystem.
+#[derive(Debug, Default)]
+pub struct ScoreStore {
+    peers: RwLock<HashMap<String, PeerRatings>>,
+}
+
+/// Computed reputation for a single peer, as returned by the API.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PeerScore {
+    pub pubkey: String,
+    /// Time-decayed Bayesian average, clamped to [1, 5].
+    pub score: f64,
+    pub total_trades: u64,
+    /// Sum of the exponential decay weights across all ratings.
+    pub weighted_count: f64,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schemars(with = "Option<String>")]
+    pub last_trade_at: Option<OffsetDateTime>,
+}
+
+impl ScoreStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a new rating for `pubkey`, ingested from a relay event.
+    pub fn record_rating(&self, pubkey: &str, stars: f64, rated_at: OffsetDateTime) {
+        let mut peers = self.peers.write().expect("score store poisoned");
+        peers
+            .entry(pubkey.to_string())
+            .or_default()
+            .ratings
+            .push(Rating { stars, rated_at });
+    }
+
+    /// Computes the current score for `pubkey`. Peers with no ratings get
+    /// the global prior mean with `total_trades = 0`.
+    pub fn score_for(&self, pubkey: &str) -> PeerScore {
+        let peers = self.peers.read().expect("score store poisoned");
+        let prior_mean = global_mean(&peers);
+        match peers.get(pubkey) {
+            Some(ratings) => score_ratings(pubkey, ratings, prior_mean),
+            None => PeerScore {
+                pubkey: pubkey.to_string(),
+                score: prior_mean,
+                total_trades: 0,
+                weighted_count: 0.0,
+                last_trade_at: None,
+            },
+        }
+    }
+
+    /// Computes scores for every known peer, used for the leaderboard.
+    pub fn all_scores(&self) -> Vec<PeerScore> {
+        let peers = self.peers.read().expect("score store poisoned");
+        let prior_mean = global_mean(&peers);
+        peers
+            .iter()
+            .map(|(pubkey, ratings)| score_ratings(pubkey, ratings, prior_mean))
+            .collect()
+    }
+}
+
+/// The Bayesian prior `m`: the plain mean of every rating ever recorded,
+/// across all peers. Falls back to `DEFAULT_PRIOR_MEAN` only while the
+/// store has no ratings at all.
+fn global_mean(peers: &HashMap<String, PeerRatings>) -> f64 {
+    let (sum, count) = peers
+        .values()
+        .flat_map(|peer| &peer.ratings)
+        .fold((0.0, 0u64), |(sum, count), rating| {
+            (sum + rating.stars, count + 1)
+        });
+
+    if count == 0 {
+        DEFAULT_PRIOR_MEAN
+    } else {
+        sum / count as f64
+    }
+}
+
+fn score_ratings(pubkey: &str, ratings: &PeerRatings, prior_mean: f64) -> PeerScore {
+    let now = OffsetDateTime::now_utc();
+
+    let mut weighted_sum = 0.0;
+    let mut weight_total = 0.0;
+    let mut last_trade_at: Option<OffsetDateTime> = None;
+
+    for rating in &ratings.ratings {
+        let age_days = (now - rating.rated_at).whole_seconds() as f64 / 86_400.0;
+        let weight = (-DECAY_LAMBDA * age_days.max(0.0)).exp();
+        weighted_sum += weight * rating.stars;
+        weight_total += weight;
+        last_trade_at = Some(match last_trade_at {
+            Some(current) if current >= rating.rated_at => current,
+            _ => rating.rated_at,
+        });
+    }
+
+    let raw_score =
+        (PRIOR_WEIGHT * prior_mean + weighted_sum) / (PRIOR_WEIGHT + weight_total);
+
+    PeerScore {
+        pubkey: pubkey.to_string(),
+        score: raw_score.clamp(1.0, 5.0),
+        total_trades: ratings.ratings.len() as u64,
+        weighted_count: weight_total,
+        last_trade_at,
+    }
+}
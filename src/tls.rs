@@ -0,0 +1,82 @@
+//! Optional HTTPS termination, so the server can run self-hosted without a
+//! reverse proxy. When `config.toml` points at a cert/key pair we bind an
+//! `axum-server` HTTPS listener with rustls, reloading the cert/key on
+//! SIGHUP so long-running deployments can rotate certificates without a
+//! restart. Otherwise the caller falls back to plain `axum::serve`.
+
+use std::net::SocketAddr;
+
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+
+use crate::config::TlsConfig;
+
+/// Serves `app` over HTTPS using the cert/key referenced by `tls`, and (if
+/// configured) a second plaintext listener that redirects to it. Watches
+/// SIGHUP to reload the cert/key in place.
+///
+/// Panics if `tls` is not enabled; callers should check
+/// [`TlsConfig::is_enabled`] first.
+pub async fn serve(addr: SocketAddr, tls: &TlsConfig, app: Router) {
+    let cert_path = tls.cert_path.as_ref().expect("tls cert_path required");
+    let key_path = tls.key_path.as_ref().expect("tls key_path required");
+
+    let rustls_config = RustlsConfig::from_pem_file(cert_path, key_path)
+        .await
+        .expect("failed to load TLS cert/key");
+
+    spawn_reload_on_sighup(rustls_config.clone(), cert_path.clone(), key_path.clone());
+
+    if let Some(redirect_port) = tls.redirect_http_port {
+        let https_port = addr.port();
+        let redirect_addr = SocketAddr::new(addr.ip(), redirect_port);
+        tokio::spawn(serve_https_redirect(redirect_addr, https_port));
+    }
+
+    axum_server::bind_rustls(addr, rustls_config)
+        .serve(app.into_make_service())
+        .await
+        .expect("HTTPS server error");
+}
+
+async fn serve_https_redirect(addr: SocketAddr, https_port: u16) {
+    let redirect = Router::new().fallback(
+        move |headers: axum::http::HeaderMap, uri: axum::http::Uri| async move {
+            // The request URI axum hands us is origin-form (path only), so
+            // the target host has to come from the `Host` header instead.
+            let host = headers
+                .get(axum::http::header::HOST)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(':').next())
+                .unwrap_or("localhost");
+            let path_and_query = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+            let location = format!("https://{host}:{https_port}{path_and_query}");
+            (
+                axum::http::StatusCode::MOVED_PERMANENTLY,
+                [(axum::http::header::LOCATION, location)],
+            )
+        },
+    );
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .expect("failed to bind HTTP redirect listener");
+    axum::serve(listener, redirect)
+        .await
+        .expect("HTTP redirect server error");
+}
+
+fn spawn_reload_on_sighup(rustls_config: RustlsConfig, cert_path: String, key_path: String) {
+    #[cfg(unix)]
+    tokio::spawn(async move {
+        let mut sighup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+            .expect("failed to install SIGHUP handler");
+        loop {
+            sighup.recv().await;
+            tracing::info!("SIGHUP received, reloading TLS certificate");
+            if let Err(err) = rustls_config.reload_from_pem_file(&cert_path, &key_path).await {
+                tracing::error!(?err, "failed to reload TLS certificate");
+            }
+        }
+    });
+}
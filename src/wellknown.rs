@@ -0,0 +1,63 @@
+//! NIP-05 identity verification, served at `/.well-known/nostr.json`.
+//!
+//! Lets peers whose reputation is shown on the leaderboard be verified
+//! against a human-readable `name@domain` identifier, per
+//! [NIP-05](https://github.com/nostr-protocol/nips/blob/master/05.md).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::extract::{Extension, Query};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{Any, CorsLayer};
+
+use crate::config::Nip05Config;
+
+#[derive(Debug, Deserialize)]
+pub struct NostrJsonQuery {
+    name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NostrJson {
+    names: HashMap<String, String>,
+}
+
+async fn nostr_json(
+    Extension(nip05): Extension<Arc<Nip05Config>>,
+    Query(query): Query<NostrJsonQuery>,
+) -> impl IntoResponse {
+    match query.name {
+        Some(name) => match nip05.names.get(&name) {
+            Some(pubkey) => {
+                let mut names = HashMap::with_capacity(1);
+                names.insert(name, pubkey.clone());
+                Json(NostrJson { names }).into_response()
+            }
+            None => StatusCode::NOT_FOUND.into_response(),
+        },
+        None => Json(NostrJson {
+            names: nip05.names.clone(),
+        })
+        .into_response(),
+    }
+}
+
+/// Builds the `/.well-known` router, to be nested under the main
+/// application router. NIP-05 requires `nostr.json` to be reachable
+/// cross-origin from any client, so this carries its own permissive CORS
+/// layer rather than relying on the app-wide one, which is restricted to
+/// `cors.allowed_origins` outside `--dev`.
+pub fn router() -> Router {
+    Router::new()
+        .route("/nostr.json", axum::routing::get(nostr_json))
+        .layer(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any),
+        )
+}